@@ -0,0 +1,172 @@
+//! Double-submit-cookie CSRF protection.
+//!
+//! Safe requests (`GET`/`HEAD`/`OPTIONS`) are passed through untouched, but get
+//! a random `csrf_token` cookie set on the way out if they don't already carry
+//! one. Mutating requests (`POST`/`PUT`/`DELETE`) must echo that cookie value
+//! back in an `X-CSRF-Token` header; the two are compared in constant time and
+//! a mismatch is rejected with a 403 [`ErrorResponse`]. This keeps the existing
+//! GET endpoints unaffected while covering the mutating routes by default.
+
+use std::future::{Ready, ready};
+use std::rc::Rc;
+
+use actix_web::cookie::{Cookie, SameSite};
+use actix_web::dev::{Service, ServiceRequest, ServiceResponse, Transform, forward_ready};
+use actix_web::http::Method;
+use actix_web::http::header::HeaderValue;
+use actix_web::{Error, HttpResponse};
+use futures_util::future::LocalBoxFuture;
+
+use crate::ErrorResponse;
+
+/// Header a client must send on mutating requests.
+const CSRF_HEADER: &str = "X-CSRF-Token";
+
+/// Number of random bytes behind each token (hex-encoded to twice the width).
+const TOKEN_BYTES: usize = 32;
+
+/// CSRF middleware factory, configurable with the cookie name and whether the
+/// issued cookie is marked `Secure`.
+#[derive(Clone)]
+pub struct Csrf {
+    cookie_name: Rc<str>,
+    secure: bool,
+}
+
+impl Csrf {
+    /// Build a middleware that issues the cookie under `cookie_name`. Set
+    /// `secure` when the service is served exclusively over HTTPS.
+    pub fn new(cookie_name: impl Into<String>, secure: bool) -> Self {
+        Csrf {
+            cookie_name: Rc::from(cookie_name.into()),
+            secure,
+        }
+    }
+}
+
+impl Default for Csrf {
+    fn default() -> Self {
+        Csrf::new("csrf_token", false)
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for Csrf
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<actix_web::body::EitherBody<B>>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = CsrfMiddleware<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(CsrfMiddleware {
+            service: Rc::new(service),
+            cookie_name: self.cookie_name.clone(),
+            secure: self.secure,
+        }))
+    }
+}
+
+/// The instantiated middleware service.
+pub struct CsrfMiddleware<S> {
+    service: Rc<S>,
+    cookie_name: Rc<str>,
+    secure: bool,
+}
+
+/// Generate a fresh, high-entropy token as a hex string.
+fn generate_token() -> String {
+    let mut bytes = [0u8; TOKEN_BYTES];
+    getrandom::getrandom(&mut bytes).expect("system RNG unavailable");
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Compare two byte slices in time independent of how early they differ.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut diff = 0u8;
+    for (x, y) in a.iter().zip(b.iter()) {
+        diff |= x ^ y;
+    }
+    diff == 0
+}
+
+/// Whether a method mutates state and therefore needs a validated token.
+fn is_mutating(method: &Method) -> bool {
+    matches!(method, &Method::POST | &Method::PUT | &Method::DELETE)
+}
+
+impl<S, B> Service<ServiceRequest> for CsrfMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<actix_web::body::EitherBody<B>>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = self.service.clone();
+        let cookie_name = self.cookie_name.clone();
+        let secure = self.secure;
+
+        let cookie_token = req
+            .cookie(&cookie_name)
+            .map(|cookie| cookie.value().to_string());
+
+        if is_mutating(req.method()) {
+            let header_token = req
+                .headers()
+                .get(CSRF_HEADER)
+                .and_then(|value| value.to_str().ok())
+                .map(|value| value.to_string());
+
+            let valid = match (&cookie_token, &header_token) {
+                (Some(cookie), Some(header)) => {
+                    constant_time_eq(cookie.as_bytes(), header.as_bytes())
+                }
+                _ => false,
+            };
+
+            if !valid {
+                return Box::pin(async move {
+                    let response = HttpResponse::Forbidden().json(ErrorResponse {
+                        detail: "CSRF token missing or invalid".to_string(),
+                    });
+                    Ok(req.into_response(response).map_into_right_body())
+                });
+            }
+
+            return Box::pin(async move {
+                let res = service.call(req).await?;
+                Ok(res.map_into_left_body())
+            });
+        }
+
+        // Safe request: issue a token cookie if the client doesn't have one.
+        let issue = cookie_token.is_none();
+        Box::pin(async move {
+            let mut res = service.call(req).await?;
+            if issue {
+                let cookie = Cookie::build(cookie_name.to_string(), generate_token())
+                    .same_site(SameSite::Strict)
+                    .secure(secure)
+                    .path("/")
+                    .http_only(false)
+                    .finish();
+                if let Ok(value) = HeaderValue::from_str(&cookie.to_string()) {
+                    res.headers_mut()
+                        .append(actix_web::http::header::SET_COOKIE, value);
+                }
+            }
+            Ok(res.map_into_left_body())
+        })
+    }
+}
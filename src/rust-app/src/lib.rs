@@ -0,0 +1,1065 @@
+use std::collections::HashMap;
+use std::io::Write as _;
+use std::sync::RwLock;
+
+use actix_multipart::Multipart;
+use actix_web::{
+    App, HttpResponse, HttpServer, Responder, get, post,
+    http::{StatusCode, header::LOCATION},
+    web,
+};
+use futures_util::StreamExt as _;
+use serde::{Deserialize, Serialize};
+use utoipa::{OpenApi, ToSchema};
+use utoipa_swagger_ui::SwaggerUi;
+
+mod csrf;
+mod live;
+
+use csrf::Csrf;
+use live::{Conditions, Forecast, ForecastEntry, LiveCache};
+
+#[derive(Clone, Deserialize, Serialize, PartialEq, Debug, ToSchema)]
+pub struct Temperature {
+    high: f64,
+    low: f64,
+}
+
+#[derive(Clone, Deserialize, Serialize, PartialEq, Debug, ToSchema)]
+pub struct ErrorResponse {
+    detail: String,
+}
+
+type WeatherData = HashMap<String, HashMap<String, HashMap<String, Temperature>>>;
+
+/// The dataset is held behind an `RwLock` so the admin upload endpoint can
+/// swap it at runtime while read endpoints keep serving the current version.
+type SharedWeather = web::Data<RwLock<WeatherData>>;
+
+const WEATHER_JSON: &str = include_str!("../../python-app/webapp/weather.json");
+
+fn load_weather_data() -> WeatherData {
+    serde_json::from_str(WEATHER_JSON).expect("Failed to parse weather data")
+}
+
+#[get("/")]
+async fn root() -> impl Responder {
+    HttpResponse::build(StatusCode::MOVED_PERMANENTLY)
+        .append_header((LOCATION, "/docs/"))
+        .finish()
+}
+
+#[get("/docs")]
+async fn docs_redirect() -> impl Responder {
+    HttpResponse::build(StatusCode::MOVED_PERMANENTLY)
+        .append_header((LOCATION, "/docs/"))
+        .finish()
+}
+
+#[utoipa::path(
+    get,
+    path = "/countries",
+    responses(
+        (status = 200, description = "List available countries", body = [String])
+    ),
+    tag = "Weather"
+)]
+#[get("/countries")]
+async fn countries(state: SharedWeather) -> impl Responder {
+    let weather = state.read().expect("weather lock poisoned");
+    let mut countries: Vec<String> = weather.keys().cloned().collect();
+    countries.sort();
+    HttpResponse::Ok().json(countries)
+}
+
+#[utoipa::path(
+    get,
+    path = "/countries/{country}",
+    params(("country" = String, Path, description = "Country whose cities are requested")),
+    responses(
+        (status = 200, description = "List cities within the country", body = [String]),
+        (status = 404, description = "Country not found", body = ErrorResponse)
+    ),
+    tag = "Weather"
+)]
+#[get("/countries/{country}")]
+async fn country_cities(state: SharedWeather, path: web::Path<String>) -> impl Responder {
+    let country = path.into_inner();
+    let weather = state.read().expect("weather lock poisoned");
+
+    let Some(cities) = weather.get(&country) else {
+        return HttpResponse::NotFound().json(ErrorResponse {
+            detail: format!("Country '{}' not found", country),
+        });
+    };
+
+    let mut city_names: Vec<String> = cities.keys().cloned().collect();
+    city_names.sort();
+
+    HttpResponse::Ok().json(city_names)
+}
+
+/// Temperature unit a caller may request. The source dataset is Fahrenheit,
+/// which stays the default for backward compatibility.
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Unit {
+    Fahrenheit,
+    Celsius,
+    Kelvin,
+}
+
+impl Unit {
+    /// Parse a query-string unit name, rejecting anything unrecognized.
+    fn parse(value: &str) -> Result<Unit, ErrorResponse> {
+        match value.to_lowercase().as_str() {
+            "fahrenheit" => Ok(Unit::Fahrenheit),
+            "celsius" => Ok(Unit::Celsius),
+            "kelvin" => Ok(Unit::Kelvin),
+            other => Err(ErrorResponse {
+                detail: format!("Unknown unit '{}'", other),
+            }),
+        }
+    }
+
+    /// The value echoed back in the response so clients know what they got.
+    fn label(self) -> &'static str {
+        match self {
+            Unit::Fahrenheit => "fahrenheit",
+            Unit::Celsius => "celsius",
+            Unit::Kelvin => "kelvin",
+        }
+    }
+}
+
+/// Convert a Fahrenheit reading into the requested unit.
+fn convert(f: f64, to: Unit) -> f64 {
+    match to {
+        Unit::Fahrenheit => f,
+        Unit::Celsius => (f - 32.0) * 5.0 / 9.0,
+        Unit::Kelvin => (f - 32.0) * 5.0 / 9.0 + 273.15,
+    }
+}
+
+/// Optional unit selector for [`monthly_average`].
+#[derive(Deserialize)]
+struct UnitParams {
+    units: Option<String>,
+}
+
+/// A monthly average with the unit its values are expressed in.
+#[derive(Clone, Serialize, PartialEq, Debug, ToSchema)]
+struct MonthlyAverage {
+    high: f64,
+    low: f64,
+    /// One of "fahrenheit", "celsius", or "kelvin".
+    unit: String,
+}
+
+#[utoipa::path(
+    get,
+    path = "/countries/{country}/{city}/{month}",
+    params(
+        ("country" = String, Path, description = "Country containing the city"),
+        ("city" = String, Path, description = "City to query"),
+        ("month" = String, Path, description = "Month with capitalized name, e.g. 'June'"),
+        ("units" = Option<String>, Query, description = "fahrenheit (default), celsius, or kelvin")
+    ),
+    responses(
+        (status = 200, description = "Monthly average temperature", body = MonthlyAverage),
+        (status = 400, description = "Unknown unit requested", body = ErrorResponse),
+        (status = 404, description = "Country, city, or month not found", body = ErrorResponse)
+    ),
+    tag = "Weather"
+)]
+#[get("/countries/{country}/{city}/{month}")]
+async fn monthly_average(
+    state: SharedWeather,
+    path: web::Path<(String, String, String)>,
+    units: web::Query<UnitParams>,
+) -> impl Responder {
+    let (country, city, month) = path.into_inner();
+
+    let unit = match &units.units {
+        Some(value) => match Unit::parse(value) {
+            Ok(unit) => unit,
+            Err(detail) => return HttpResponse::BadRequest().json(detail),
+        },
+        None => Unit::Fahrenheit,
+    };
+
+    let weather = state.read().expect("weather lock poisoned");
+
+    let Some(cities) = weather.get(&country) else {
+        return HttpResponse::NotFound().json(ErrorResponse {
+            detail: format!("Country '{}' not found", country),
+        });
+    };
+
+    let Some(months) = cities.get(&city) else {
+        return HttpResponse::NotFound().json(ErrorResponse {
+            detail: format!("City '{}' not found in country '{}'", city, country),
+        });
+    };
+
+    let Some(temperature) = months.get(&month) else {
+        return HttpResponse::NotFound().json(ErrorResponse {
+            detail: format!(
+                "Month '{}' not found for city '{}' in country '{}'",
+                month, city, country
+            ),
+        });
+    };
+
+    HttpResponse::Ok().json(MonthlyAverage {
+        high: convert(temperature.high, unit),
+        low: convert(temperature.low, unit),
+        unit: unit.label().to_string(),
+    })
+}
+
+/// How a candidate matched the query, most-relevant first.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum MatchKind {
+    Exact,
+    Prefix,
+    Fuzzy,
+}
+
+/// A ranked search result across country and city names.
+#[derive(Clone, Serialize, PartialEq, Debug, ToSchema)]
+struct SearchHit {
+    /// Either "country" or "city".
+    kind: String,
+    /// Matched country, or the country a matched city belongs to.
+    country: String,
+    /// Matched city, or `None` when the hit is a country.
+    city: Option<String>,
+    /// Edit distance from the query (0 for exact/prefix matches).
+    score: usize,
+}
+
+/// Query parameters for the fuzzy search endpoint.
+#[derive(Deserialize)]
+struct SearchParams {
+    q: String,
+    limit: Option<usize>,
+}
+
+/// Largest edit distance tolerated for a query of the given length. Short
+/// queries must match exactly; longer ones gain slack so typos still land.
+fn edit_cap(query_len: usize) -> usize {
+    match query_len {
+        0..=4 => 0,
+        5..=8 => 1,
+        _ => 2,
+    }
+}
+
+/// Levenshtein edit distance with an early exit once `cap` is exceeded, in
+/// which case `cap + 1` is returned to signal "too far".
+fn bounded_levenshtein(a: &str, b: &str, cap: usize) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    if a.len().abs_diff(b.len()) > cap {
+        return cap + 1;
+    }
+
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut curr = vec![0; b.len() + 1];
+    for (i, &ca) in a.iter().enumerate() {
+        curr[0] = i + 1;
+        let mut row_min = curr[0];
+        for (j, &cb) in b.iter().enumerate() {
+            let cost = if ca == cb { 0 } else { 1 };
+            curr[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(curr[j] + 1);
+            row_min = row_min.min(curr[j + 1]);
+        }
+        if row_min > cap {
+            return cap + 1;
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+    prev[b.len()]
+}
+
+/// Classify a single candidate against the normalized query.
+fn classify(query: &str, candidate: &str, cap: usize) -> Option<(MatchKind, usize)> {
+    let lowered = candidate.to_lowercase();
+    if lowered == query {
+        return Some((MatchKind::Exact, 0));
+    }
+    if lowered.starts_with(query) {
+        return Some((MatchKind::Prefix, 0));
+    }
+    let distance = bounded_levenshtein(query, &lowered, cap);
+    (distance <= cap).then_some((MatchKind::Fuzzy, distance))
+}
+
+#[utoipa::path(
+    get,
+    path = "/search",
+    params(
+        ("q" = String, Query, description = "Search term, matched with typo tolerance"),
+        ("limit" = Option<usize>, Query, description = "Maximum number of hits to return")
+    ),
+    responses(
+        (status = 200, description = "Ranked matches", body = [SearchHit]),
+        (status = 400, description = "Missing or empty query", body = ErrorResponse)
+    ),
+    tag = "Weather"
+)]
+#[get("/search")]
+async fn search(state: SharedWeather, query: web::Query<SearchParams>) -> impl Responder {
+    let term = query.q.trim().to_lowercase();
+    if term.is_empty() {
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            detail: "Query parameter 'q' must not be empty".to_string(),
+        });
+    }
+    let cap = edit_cap(term.chars().count());
+
+    let weather = state.read().expect("weather lock poisoned");
+    let mut hits: Vec<(MatchKind, usize, SearchHit)> = Vec::new();
+    for (country, cities) in weather.iter() {
+        if let Some((kind, score)) = classify(&term, country, cap) {
+            hits.push((
+                kind,
+                score,
+                SearchHit {
+                    kind: "country".to_string(),
+                    country: country.clone(),
+                    city: None,
+                    score,
+                },
+            ));
+        }
+        for city in cities.keys() {
+            if let Some((kind, score)) = classify(&term, city, cap) {
+                hits.push((
+                    kind,
+                    score,
+                    SearchHit {
+                        kind: "city".to_string(),
+                        country: country.clone(),
+                        city: Some(city.clone()),
+                        score,
+                    },
+                ));
+            }
+        }
+    }
+
+    // Rank by match type, then edit distance, then alphabetically.
+    hits.sort_by(|a, b| {
+        a.0.cmp(&b.0)
+            .then(a.1.cmp(&b.1))
+            .then_with(|| (&a.2.country, &a.2.city).cmp(&(&b.2.country, &b.2.city)))
+    });
+
+    let mut results: Vec<SearchHit> = hits.into_iter().map(|(_, _, hit)| hit).collect();
+    if let Some(limit) = query.limit {
+        results.truncate(limit);
+    }
+
+    HttpResponse::Ok().json(results)
+}
+
+/// Output format for the [`export`] endpoint.
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum ExportFormat {
+    Csv,
+    Ndjson,
+}
+
+impl ExportFormat {
+    /// Parse the `format` query value, defaulting to CSV when omitted.
+    fn parse(value: Option<&str>) -> Result<ExportFormat, ErrorResponse> {
+        match value {
+            None | Some("csv") => Ok(ExportFormat::Csv),
+            Some("ndjson") => Ok(ExportFormat::Ndjson),
+            Some(other) => Err(ErrorResponse {
+                detail: format!("Unknown export format '{}'", other),
+            }),
+        }
+    }
+
+    fn content_type(self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "text/csv",
+            ExportFormat::Ndjson => "application/x-ndjson",
+        }
+    }
+
+    fn filename(self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "weather.csv",
+            ExportFormat::Ndjson => "weather.ndjson",
+        }
+    }
+}
+
+/// Query parameters for [`export`].
+#[derive(Deserialize)]
+struct ExportParams {
+    format: Option<String>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/export",
+    params(("format" = Option<String>, Query, description = "csv (default) or ndjson")),
+    responses(
+        (status = 200, description = "Streamed flat dataset"),
+        (status = 400, description = "Unknown format requested", body = ErrorResponse)
+    ),
+    tag = "Weather"
+)]
+#[get("/export")]
+async fn export(state: SharedWeather, params: web::Query<ExportParams>) -> impl Responder {
+    let format = match ExportFormat::parse(params.format.as_deref()) {
+        Ok(format) => format,
+        Err(detail) => return HttpResponse::BadRequest().json(detail),
+    };
+
+    // Flatten the nested map into owned rows up front so the stream body can
+    // outlive the read lock without holding it across yields.
+    let weather = state.read().expect("weather lock poisoned");
+    let mut rows: Vec<(String, String, String, f64, f64)> = Vec::new();
+    for (country, cities) in weather.iter() {
+        for (city, months) in cities.iter() {
+            for (month, temp) in months.iter() {
+                rows.push((
+                    country.clone(),
+                    city.clone(),
+                    month.clone(),
+                    temp.high,
+                    temp.low,
+                ));
+            }
+        }
+    }
+    drop(weather);
+
+    // Yield one serialized row per chunk, so the serialized output is never
+    // built up as a whole. The flattened `rows` above do hold a copy of every
+    // row's values for as long as the response streams.
+    let header = match format {
+        ExportFormat::Csv => Some(Ok::<_, actix_web::Error>(web::Bytes::from_static(
+            b"country,city,month,high,low\n",
+        ))),
+        ExportFormat::Ndjson => None,
+    };
+    let body = futures_util::stream::iter(header.into_iter().chain(rows.into_iter().map(
+        move |(country, city, month, high, low)| {
+            let line = match format {
+                ExportFormat::Csv => format!(
+                    "{},{},{},{},{}\n",
+                    csv_field(&country),
+                    csv_field(&city),
+                    csv_field(&month),
+                    high,
+                    low
+                ),
+                ExportFormat::Ndjson => format!(
+                    "{{\"country\":{},\"city\":{},\"month\":{},\"high\":{},\"low\":{}}}\n",
+                    json_string(&country),
+                    json_string(&city),
+                    json_string(&month),
+                    high,
+                    low
+                ),
+            };
+            Ok::<_, actix_web::Error>(web::Bytes::from(line))
+        },
+    )));
+
+    HttpResponse::Ok()
+        .content_type(format.content_type())
+        .append_header((
+            actix_web::http::header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{}\"", format.filename()),
+        ))
+        .streaming(body)
+}
+
+/// Render a string as a JSON string literal (with surrounding quotes).
+fn json_string(value: &str) -> String {
+    serde_json::to_string(value).expect("string serialization cannot fail")
+}
+
+/// Render a field for a CSV row following RFC 4180: if it contains a comma,
+/// double-quote, or newline, wrap it in double-quotes and double any embedded
+/// quotes. Country and city names come from the admin upload, so a key with a
+/// comma or quote must not be allowed to shift columns.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Summary returned after a successful dataset replacement.
+#[derive(Clone, Serialize, PartialEq, Debug, ToSchema)]
+struct UploadSummary {
+    /// Number of countries in the newly loaded dataset.
+    countries: usize,
+    /// Total number of cities across all countries.
+    cities: usize,
+}
+
+#[utoipa::path(
+    post,
+    path = "/admin/weather",
+    request_body(content = String, description = "Replacement weather.json", content_type = "multipart/form-data"),
+    responses(
+        (status = 200, description = "Dataset replaced", body = UploadSummary),
+        (status = 400, description = "Missing or invalid upload", body = ErrorResponse)
+    ),
+    tag = "Admin"
+)]
+#[post("/admin/weather")]
+async fn replace_weather(state: SharedWeather, mut payload: Multipart) -> impl Responder {
+    // Stream the uploaded part to a temp file before touching the live data,
+    // so a malformed upload can't leave the dataset half-written.
+    let mut temp = match tempfile::NamedTempFile::new() {
+        Ok(temp) => temp,
+        Err(e) => {
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                detail: format!("Could not create temporary file: {}", e),
+            });
+        }
+    };
+
+    let mut received = false;
+    while let Some(field) = payload.next().await {
+        let mut field = match field {
+            Ok(field) => field,
+            Err(e) => {
+                return HttpResponse::BadRequest().json(ErrorResponse {
+                    detail: format!("Malformed multipart payload: {}", e),
+                });
+            }
+        };
+        received = true;
+        while let Some(chunk) = field.next().await {
+            match chunk {
+                Ok(bytes) => {
+                    if let Err(e) = temp.write_all(&bytes) {
+                        return HttpResponse::InternalServerError().json(ErrorResponse {
+                            detail: format!("Could not buffer upload: {}", e),
+                        });
+                    }
+                }
+                Err(e) => {
+                    return HttpResponse::BadRequest().json(ErrorResponse {
+                        detail: format!("Error reading upload: {}", e),
+                    });
+                }
+            }
+        }
+    }
+
+    if !received {
+        return HttpResponse::BadRequest().json(ErrorResponse {
+            detail: "No file part found in multipart upload".to_string(),
+        });
+    }
+
+    let file = match std::fs::File::open(temp.path()) {
+        Ok(file) => file,
+        Err(e) => {
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                detail: format!("Could not reopen upload: {}", e),
+            });
+        }
+    };
+    let new_data: WeatherData = match serde_json::from_reader(file) {
+        Ok(data) => data,
+        Err(e) => {
+            return HttpResponse::BadRequest().json(ErrorResponse {
+                detail: format!("Uploaded file is not valid weather data: {}", e),
+            });
+        }
+    };
+
+    let summary = UploadSummary {
+        countries: new_data.len(),
+        cities: new_data.values().map(|cities| cities.len()).sum(),
+    };
+
+    *state.write().expect("weather lock poisoned") = new_data;
+
+    HttpResponse::Ok().json(summary)
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        countries,
+        country_cities,
+        monthly_average,
+        search,
+        export,
+        replace_weather,
+        live::current,
+        live::forecast
+    ),
+    components(schemas(
+        Temperature,
+        MonthlyAverage,
+        ErrorResponse,
+        SearchHit,
+        UploadSummary,
+        Conditions,
+        Forecast,
+        ForecastEntry
+    )),
+    tags(
+        (name = "Weather", description = "Weather data endpoints"),
+        (name = "Admin", description = "Dataset administration"),
+        (name = "Live", description = "On-demand OpenWeatherMap passthrough")
+    )
+)]
+struct ApiDoc;
+
+/// Build the fully-configured `App`, wiring the middleware, shared state, and
+/// every route into a single definition of the service graph. Both the binary
+/// and the integration tests construct the server through this function so
+/// there is exactly one place the graph is described.
+pub fn build_app(
+    weather: web::Data<RwLock<WeatherData>>,
+    live_cache: web::Data<LiveCache>,
+) -> App<
+    impl actix_web::dev::ServiceFactory<
+        actix_web::dev::ServiceRequest,
+        Config = (),
+        Response = actix_web::dev::ServiceResponse,
+        Error = actix_web::Error,
+        InitError = (),
+    >,
+> {
+    App::new()
+        .wrap(Csrf::default())
+        .app_data(weather)
+        .app_data(live_cache)
+        .service(root)
+        .service(docs_redirect)
+        .service(countries)
+        .service(country_cities)
+        .service(monthly_average)
+        .service(search)
+        .service(export)
+        .service(replace_weather)
+        .service(live::current)
+        .service(live::forecast)
+        .service(SwaggerUi::new("/docs/{_:.*}").url("/api-doc/openapi.json", ApiDoc::openapi()))
+}
+
+/// Bind the server to `addr` and return the unstarted [`Server`]. Callers
+/// `await` the returned handle to run it; passing port `0` binds an ephemeral
+/// port. The bound port is not returned; the integration tests bind their
+/// own listener and go through [`run_listener`] instead.
+///
+/// [`Server`]: actix_web::dev::Server
+pub fn run(addr: impl std::net::ToSocketAddrs) -> std::io::Result<actix_web::dev::Server> {
+    let weather = web::Data::new(RwLock::new(load_weather_data()));
+    let live_cache = web::Data::new(LiveCache::default());
+
+    let server = HttpServer::new(move || build_app(weather.clone(), live_cache.clone()))
+        .bind(addr)?
+        .run();
+
+    Ok(server)
+}
+
+/// Like [`run`], but serve on an already-bound [`TcpListener`] instead of
+/// binding internally. The blackbox integration tests bind port `0`
+/// themselves, read the ephemeral port back off the listener, and hand it
+/// here so they can drive the running service over a real socket.
+///
+/// [`TcpListener`]: std::net::TcpListener
+pub fn run_listener(
+    listener: std::net::TcpListener,
+) -> std::io::Result<actix_web::dev::Server> {
+    let weather = web::Data::new(RwLock::new(load_weather_data()));
+    let live_cache = web::Data::new(LiveCache::default());
+
+    let server = HttpServer::new(move || build_app(weather.clone(), live_cache.clone()))
+        .listen(listener)?
+        .run();
+
+    Ok(server)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::{
+        App,
+        http::{StatusCode, header::LOCATION},
+        test,
+    };
+
+    fn init_app() -> actix_web::App<
+        impl actix_web::dev::ServiceFactory<
+            actix_web::dev::ServiceRequest,
+            Config = (),
+            Response = actix_web::dev::ServiceResponse,
+            Error = actix_web::Error,
+            InitError = (),
+        >,
+    > {
+        let weather = web::Data::new(RwLock::new(load_weather_data()));
+        let live_cache = web::Data::new(LiveCache::default());
+        build_app(weather, live_cache)
+    }
+
+    #[actix_web::test]
+    async fn root_redirects_to_docs() {
+        let app = test::init_service(init_app()).await;
+
+        let resp = test::call_service(&app, test::TestRequest::with_uri("/").to_request()).await;
+        assert_eq!(resp.status(), StatusCode::MOVED_PERMANENTLY);
+        let location = resp
+            .headers()
+            .get(LOCATION)
+            .expect("missing LOCATION header");
+        assert_eq!(location, "/docs/");
+    }
+
+    #[actix_web::test]
+    async fn docs_redirects_to_trailing_slash() {
+        let app = test::init_service(init_app()).await;
+
+        let resp =
+            test::call_service(&app, test::TestRequest::with_uri("/docs").to_request()).await;
+        assert_eq!(resp.status(), StatusCode::MOVED_PERMANENTLY);
+        let location = resp
+            .headers()
+            .get(LOCATION)
+            .expect("missing LOCATION header");
+        assert_eq!(location, "/docs/");
+    }
+
+    #[actix_web::test]
+    async fn docs_serves_swagger_ui() {
+        let app = test::init_service(init_app()).await;
+
+        let resp =
+            test::call_service(&app, test::TestRequest::with_uri("/docs/").to_request()).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = test::read_body(resp).await;
+        let body = String::from_utf8(body.to_vec()).expect("docs response not utf8");
+        assert!(body.contains("Swagger UI"));
+    }
+
+    #[actix_web::test]
+    async fn countries_returns_sorted_list() {
+        let app = test::init_service(init_app()).await;
+
+        let body: Vec<String> = test::call_and_read_body_json(
+            &app,
+            test::TestRequest::with_uri("/countries").to_request(),
+        )
+        .await;
+
+        assert_eq!(
+            body,
+            vec![
+                "England", "France", "Germany", "Italy", "Peru", "Portugal", "Spain"
+            ]
+        );
+    }
+
+    #[actix_web::test]
+    async fn country_cities_success() {
+        let app = test::init_service(init_app()).await;
+
+        let body: Vec<String> = test::call_and_read_body_json(
+            &app,
+            test::TestRequest::with_uri("/countries/Spain").to_request(),
+        )
+        .await;
+
+        assert_eq!(body, vec!["Seville".to_string()]);
+    }
+
+    #[actix_web::test]
+    async fn country_cities_not_found() {
+        let app = test::init_service(init_app()).await;
+
+        let resp = test::call_service(
+            &app,
+            test::TestRequest::with_uri("/countries/Unknownland").to_request(),
+        )
+        .await;
+
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+        let body: ErrorResponse = test::read_body_json(resp).await;
+        assert_eq!(
+            body,
+            ErrorResponse {
+                detail: "Country 'Unknownland' not found".to_string()
+            }
+        );
+    }
+
+    #[actix_web::test]
+    async fn monthly_average_success() {
+        let app = test::init_service(init_app()).await;
+
+        let body: Temperature = test::call_and_read_body_json(
+            &app,
+            test::TestRequest::with_uri("/countries/England/London/January").to_request(),
+        )
+        .await;
+
+        assert_eq!(
+            body,
+            Temperature {
+                high: 45.0,
+                low: 36.0,
+            }
+        );
+    }
+
+    #[actix_web::test]
+    async fn replace_weather_swaps_dataset() {
+        let app = test::init_service(init_app()).await;
+
+        // A minimal replacement dataset introducing a brand new country.
+        let boundary = "testboundary";
+        let json = r#"{"Narnia":{"Cair Paravel":{"June":{"high":70,"low":55}}}}"#;
+        let body = format!(
+            "--{boundary}\r\nContent-Disposition: form-data; name=\"file\"; \
+             filename=\"weather.json\"\r\nContent-Type: application/json\r\n\r\n{json}\r\n\
+             --{boundary}--\r\n"
+        );
+
+        let summary: UploadSummary = test::call_and_read_body_json(
+            &app,
+            test::TestRequest::post()
+                .uri("/admin/weather")
+                .cookie(actix_web::cookie::Cookie::new("csrf_token", "token"))
+                .insert_header(("X-CSRF-Token", "token"))
+                .insert_header((
+                    "content-type",
+                    format!("multipart/form-data; boundary={boundary}"),
+                ))
+                .set_payload(body)
+                .to_request(),
+        )
+        .await;
+        assert_eq!(summary, UploadSummary { countries: 1, cities: 1 });
+
+        // The newly uploaded country is immediately queryable.
+        let cities: Vec<String> = test::call_and_read_body_json(
+            &app,
+            test::TestRequest::with_uri("/countries/Narnia").to_request(),
+        )
+        .await;
+        assert_eq!(cities, vec!["Cair Paravel".to_string()]);
+    }
+
+    #[actix_web::test]
+    async fn replace_weather_rejects_invalid_json() {
+        let app = test::init_service(init_app()).await;
+
+        let boundary = "testboundary";
+        let body = format!(
+            "--{boundary}\r\nContent-Disposition: form-data; name=\"file\"; \
+             filename=\"weather.json\"\r\nContent-Type: application/json\r\n\r\nnot json\r\n\
+             --{boundary}--\r\n"
+        );
+
+        let resp = test::call_service(
+            &app,
+            test::TestRequest::post()
+                .uri("/admin/weather")
+                .cookie(actix_web::cookie::Cookie::new("csrf_token", "token"))
+                .insert_header(("X-CSRF-Token", "token"))
+                .insert_header((
+                    "content-type",
+                    format!("multipart/form-data; boundary={boundary}"),
+                ))
+                .set_payload(body)
+                .to_request(),
+        )
+        .await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[actix_web::test]
+    async fn export_csv_contains_known_row() {
+        let app = test::init_service(init_app()).await;
+
+        let resp =
+            test::call_service(&app, test::TestRequest::with_uri("/export").to_request()).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = test::read_body(resp).await;
+        let body = String::from_utf8(body.to_vec()).expect("export not utf8");
+
+        assert!(body.starts_with("country,city,month,high,low\n"));
+        assert!(
+            body.contains("England,London,January,45,36"),
+            "missing known row in export"
+        );
+    }
+
+    #[test]
+    fn csv_field_quotes_and_escapes_special_characters() {
+        assert_eq!(csv_field("London"), "London");
+        assert_eq!(csv_field("Paris, Ile-de-France"), "\"Paris, Ile-de-France\"");
+        assert_eq!(csv_field("a\"b"), "\"a\"\"b\"");
+        assert_eq!(csv_field("line\nbreak"), "\"line\nbreak\"");
+    }
+
+    #[actix_web::test]
+    async fn export_rejects_unknown_format() {
+        let app = test::init_service(init_app()).await;
+
+        let resp = test::call_service(
+            &app,
+            test::TestRequest::with_uri("/export?format=xml").to_request(),
+        )
+        .await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[actix_web::test]
+    async fn csrf_rejects_post_without_token() {
+        let app = test::init_service(init_app()).await;
+
+        let resp = test::call_service(
+            &app,
+            test::TestRequest::post().uri("/admin/weather").to_request(),
+        )
+        .await;
+
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[actix_web::test]
+    async fn csrf_sets_cookie_on_safe_request() {
+        let app = test::init_service(init_app()).await;
+
+        let resp =
+            test::call_service(&app, test::TestRequest::with_uri("/countries").to_request()).await;
+        let set_cookie = resp
+            .headers()
+            .get(actix_web::http::header::SET_COOKIE)
+            .expect("missing Set-Cookie header");
+        let set_cookie = set_cookie.to_str().expect("cookie not utf8");
+        assert!(set_cookie.contains("csrf_token="));
+        assert!(set_cookie.contains("SameSite=Strict"));
+    }
+
+    #[actix_web::test]
+    async fn monthly_average_celsius_conversion() {
+        let app = test::init_service(init_app()).await;
+
+        let body: MonthlyAverage = test::call_and_read_body_json(
+            &app,
+            test::TestRequest::with_uri("/countries/England/London/January?units=celsius")
+                .to_request(),
+        )
+        .await;
+
+        assert_eq!(body.unit, "celsius");
+        assert!((body.high - 7.222).abs() < 0.01, "got {}", body.high);
+        assert!((body.low - 2.222).abs() < 0.01, "got {}", body.low);
+    }
+
+    #[actix_web::test]
+    async fn monthly_average_unknown_unit_rejected() {
+        let app = test::init_service(init_app()).await;
+
+        let resp = test::call_service(
+            &app,
+            test::TestRequest::with_uri("/countries/England/London/January?units=rankine")
+                .to_request(),
+        )
+        .await;
+
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[actix_web::test]
+    async fn search_exact_country() {
+        let app = test::init_service(init_app()).await;
+
+        let body: Vec<SearchHit> = test::call_and_read_body_json(
+            &app,
+            test::TestRequest::with_uri("/search?q=Spain").to_request(),
+        )
+        .await;
+
+        assert_eq!(
+            body.first(),
+            Some(&SearchHit {
+                kind: "country".to_string(),
+                country: "Spain".to_string(),
+                city: None,
+                score: 0,
+            })
+        );
+    }
+
+    #[actix_web::test]
+    async fn search_tolerates_typo() {
+        let app = test::init_service(init_app()).await;
+
+        let body: Vec<SearchHit> = test::call_and_read_body_json(
+            &app,
+            test::TestRequest::with_uri("/search?q=Lundon").to_request(),
+        )
+        .await;
+
+        assert!(
+            body.iter()
+                .any(|hit| hit.city.as_deref() == Some("London")),
+            "expected a fuzzy match for 'London', got {body:?}"
+        );
+    }
+
+    #[actix_web::test]
+    async fn search_empty_query_rejected() {
+        let app = test::init_service(init_app()).await;
+
+        let resp = test::call_service(
+            &app,
+            test::TestRequest::with_uri("/search?q=%20").to_request(),
+        )
+        .await;
+
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[test]
+    fn bounded_levenshtein_caps_out() {
+        assert_eq!(bounded_levenshtein("kitten", "sitting", 1), 2);
+        assert_eq!(bounded_levenshtein("kitten", "kitten", 0), 0);
+    }
+
+    #[actix_web::test]
+    async fn monthly_average_missing_month() {
+        let app = test::init_service(init_app()).await;
+
+        let resp = test::call_service(
+            &app,
+            test::TestRequest::with_uri("/countries/England/London/NotAMonth").to_request(),
+        )
+        .await;
+
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+        let body: ErrorResponse = test::read_body_json(resp).await;
+        assert_eq!(
+            body,
+            ErrorResponse {
+                detail: "Month 'NotAMonth' not found for city 'London' in country 'England'"
+                    .to_string()
+            }
+        );
+    }
+}
@@ -0,0 +1,408 @@
+//! Live weather backed by openweathermap.org.
+//!
+//! Unlike the rest of the API, which serves the frozen dataset embedded at
+//! compile time, these endpoints fetch current conditions and short-range
+//! forecasts on demand. The three query modes mirror the `weather_util_rust`
+//! CLI: zipcode + country code, city name, or a latitude/longitude pair.
+//! Responses are cached for a short TTL, keyed by the resolved location, so a
+//! burst of identical requests does not repeatedly hit the upstream.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use actix_web::{HttpResponse, Responder, get, web};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::{ErrorResponse, Temperature};
+
+/// Base URL of the OpenWeatherMap 2.5 API.
+const OWM_BASE: &str = "https://api.openweathermap.org/data/2.5";
+
+/// How long a fetched response stays fresh in the cache.
+const CACHE_TTL: Duration = Duration::from_secs(300);
+
+/// Current conditions for a single location, mapped from the upstream payload
+/// into the same [`Temperature`] shape the static endpoints expose.
+#[derive(Clone, Serialize, PartialEq, Debug, ToSchema)]
+pub struct Conditions {
+    /// High/low temperatures in the upstream's configured units.
+    pub temperature: Temperature,
+    /// Human-readable summary, e.g. "light rain".
+    pub description: String,
+    /// Relative humidity as a percentage.
+    pub humidity: u8,
+    /// Wind speed in the upstream's configured units.
+    pub wind_speed: f64,
+}
+
+/// A single forecast data point.
+#[derive(Clone, Serialize, PartialEq, Debug, ToSchema)]
+pub struct ForecastEntry {
+    /// Unix timestamp of the forecasted slot.
+    pub timestamp: i64,
+    /// High/low temperatures for the slot.
+    pub temperature: Temperature,
+    /// Human-readable summary for the slot.
+    pub description: String,
+}
+
+/// A short-range forecast: an ordered list of [`ForecastEntry`] values.
+#[derive(Clone, Serialize, PartialEq, Debug, ToSchema)]
+pub struct Forecast {
+    /// Forecast slots in chronological order.
+    pub entries: Vec<ForecastEntry>,
+}
+
+/// The query modes accepted by the live endpoints, matching the
+/// `weather_util_rust` CLI. Exactly one mode must be fully specified.
+#[derive(Deserialize)]
+pub struct LiveQuery {
+    lat: Option<f64>,
+    lon: Option<f64>,
+    zipcode: Option<String>,
+    country_code: Option<String>,
+    city_name: Option<String>,
+}
+
+/// A resolved location, ready to be turned into upstream query parameters.
+enum Location {
+    ZipCode { zipcode: String, country_code: String },
+    CityName(String),
+    Coords { lat: f64, lon: f64 },
+}
+
+impl LiveQuery {
+    /// Resolve the query into a single [`Location`], rejecting ambiguous or
+    /// empty input with the standard [`ErrorResponse`] 400 pattern.
+    fn resolve(&self) -> Result<Location, ErrorResponse> {
+        match (self.lat, self.lon, &self.zipcode, &self.city_name) {
+            (Some(lat), Some(lon), None, None) if self.country_code.is_none() => {
+                Ok(Location::Coords { lat, lon })
+            }
+            (None, None, Some(zipcode), None) => {
+                let Some(country_code) = &self.country_code else {
+                    return Err(ErrorResponse {
+                        detail: "'zipcode' requires 'country_code'".to_string(),
+                    });
+                };
+                Ok(Location::ZipCode {
+                    zipcode: zipcode.clone(),
+                    country_code: country_code.clone(),
+                })
+            }
+            (None, None, None, Some(city_name)) if self.country_code.is_none() => {
+                Ok(Location::CityName(city_name.clone()))
+            }
+            _ => Err(ErrorResponse {
+                detail: "supply exactly one of: 'lat'+'lon', 'zipcode'+'country_code', or \
+                         'city_name'"
+                    .to_string(),
+            }),
+        }
+    }
+}
+
+impl Location {
+    /// Upstream query parameters for this location.
+    fn params(&self) -> Vec<(&'static str, String)> {
+        match self {
+            Location::Coords { lat, lon } => {
+                vec![("lat", lat.to_string()), ("lon", lon.to_string())]
+            }
+            Location::ZipCode {
+                zipcode,
+                country_code,
+            } => vec![("zip", format!("{},{}", zipcode, country_code))],
+            Location::CityName(city_name) => vec![("q", city_name.clone())],
+        }
+    }
+
+    /// Stable cache key. Coordinates are rounded to two decimals so nearby
+    /// requests share an entry; the other modes key on their raw terms.
+    fn cache_key(&self) -> String {
+        match self {
+            Location::Coords { lat, lon } => format!("coords:{:.2},{:.2}", lat, lon),
+            Location::ZipCode {
+                zipcode,
+                country_code,
+            } => format!("zip:{},{}", zipcode, country_code),
+            Location::CityName(city_name) => format!("city:{}", city_name.to_lowercase()),
+        }
+    }
+}
+
+/// A tiny TTL cache of fetched responses, shared across requests. Current
+/// conditions and forecasts are kept in parallel location-keyed maps so a
+/// burst of identical requests of either kind does not re-hit the upstream.
+/// The outbound [`reqwest::Client`] lives here too so its connection pool is
+/// reused across calls rather than rebuilt per request.
+pub struct LiveCache {
+    conditions: Mutex<HashMap<String, (Instant, Conditions)>>,
+    forecasts: Mutex<HashMap<String, (Instant, Forecast)>>,
+    ttl: Duration,
+    client: reqwest::Client,
+}
+
+impl Default for LiveCache {
+    fn default() -> Self {
+        LiveCache {
+            conditions: Mutex::new(HashMap::new()),
+            forecasts: Mutex::new(HashMap::new()),
+            ttl: CACHE_TTL,
+            client: reqwest::Client::new(),
+        }
+    }
+}
+
+/// Return a still-fresh entry from a cache map, if any.
+fn get_fresh<T: Clone>(
+    map: &Mutex<HashMap<String, (Instant, T)>>,
+    key: &str,
+    ttl: Duration,
+) -> Option<T> {
+    let entries = map.lock().expect("live cache poisoned");
+    entries
+        .get(key)
+        .and_then(|(stored, value)| (stored.elapsed() < ttl).then(|| value.clone()))
+}
+
+/// Record a freshly fetched entry, pruning any expired entries first so the
+/// map does not grow without bound as locations come and go.
+fn put_entry<T>(map: &Mutex<HashMap<String, (Instant, T)>>, key: String, value: T, ttl: Duration) {
+    let mut entries = map.lock().expect("live cache poisoned");
+    entries.retain(|_, (stored, _)| stored.elapsed() < ttl);
+    entries.insert(key, (Instant::now(), value));
+}
+
+impl LiveCache {
+    /// Return a still-fresh cached [`Conditions`] entry, if any.
+    fn get_conditions(&self, key: &str) -> Option<Conditions> {
+        get_fresh(&self.conditions, key, self.ttl)
+    }
+
+    /// Record freshly fetched [`Conditions`].
+    fn put_conditions(&self, key: String, conditions: Conditions) {
+        put_entry(&self.conditions, key, conditions, self.ttl);
+    }
+
+    /// Return a still-fresh cached [`Forecast`] entry, if any.
+    fn get_forecast(&self, key: &str) -> Option<Forecast> {
+        get_fresh(&self.forecasts, key, self.ttl)
+    }
+
+    /// Record a freshly fetched [`Forecast`].
+    fn put_forecast(&self, key: String, forecast: Forecast) {
+        put_entry(&self.forecasts, key, forecast, self.ttl);
+    }
+}
+
+/// Read the API key from the environment, mapping absence to a 500-style error.
+fn api_key() -> Result<String, ErrorResponse> {
+    std::env::var("OPENWEATHER_API_KEY").map_err(|_| ErrorResponse {
+        detail: "OPENWEATHER_API_KEY is not configured".to_string(),
+    })
+}
+
+/// Shapes of the upstream payloads we consume, trimmed to the fields we map.
+mod owm {
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    pub struct Current {
+        pub main: Main,
+        pub weather: Vec<Weather>,
+        pub wind: Wind,
+    }
+
+    #[derive(Deserialize)]
+    pub struct ForecastResponse {
+        pub list: Vec<ForecastSlot>,
+    }
+
+    #[derive(Deserialize)]
+    pub struct ForecastSlot {
+        pub dt: i64,
+        pub main: Main,
+        pub weather: Vec<Weather>,
+    }
+
+    #[derive(Deserialize)]
+    pub struct Main {
+        pub temp_max: f64,
+        pub temp_min: f64,
+        pub humidity: u8,
+    }
+
+    #[derive(Deserialize)]
+    pub struct Weather {
+        pub description: String,
+    }
+
+    #[derive(Deserialize)]
+    pub struct Wind {
+        pub speed: f64,
+    }
+}
+
+impl From<owm::Current> for Conditions {
+    fn from(current: owm::Current) -> Self {
+        Conditions {
+            temperature: Temperature {
+                high: current.main.temp_max,
+                low: current.main.temp_min,
+            },
+            description: current
+                .weather
+                .into_iter()
+                .next()
+                .map(|w| w.description)
+                .unwrap_or_default(),
+            humidity: current.main.humidity,
+            wind_speed: current.wind.speed,
+        }
+    }
+}
+
+impl From<owm::ForecastResponse> for Forecast {
+    fn from(response: owm::ForecastResponse) -> Self {
+        let entries = response
+            .list
+            .into_iter()
+            .map(|slot| ForecastEntry {
+                timestamp: slot.dt,
+                temperature: Temperature {
+                    high: slot.main.temp_max,
+                    low: slot.main.temp_min,
+                },
+                description: slot
+                    .weather
+                    .into_iter()
+                    .next()
+                    .map(|w| w.description)
+                    .unwrap_or_default(),
+            })
+            .collect();
+        Forecast { entries }
+    }
+}
+
+/// Perform an upstream GET and deserialize it, mapping transport/decoding
+/// failures onto [`ErrorResponse`].
+async fn fetch<T: serde::de::DeserializeOwned>(
+    client: &reqwest::Client,
+    endpoint: &str,
+    mut params: Vec<(&'static str, String)>,
+    key: &str,
+) -> Result<T, ErrorResponse> {
+    params.push(("appid", key.to_string()));
+    let response = client
+        .get(format!("{}/{}", OWM_BASE, endpoint))
+        .query(&params)
+        .send()
+        .await
+        .map_err(|e| ErrorResponse {
+            detail: format!("upstream request failed: {}", e),
+        })?;
+
+    if !response.status().is_success() {
+        return Err(ErrorResponse {
+            detail: format!("upstream returned status {}", response.status()),
+        });
+    }
+
+    response.json::<T>().await.map_err(|e| ErrorResponse {
+        detail: format!("could not decode upstream response: {}", e),
+    })
+}
+
+#[utoipa::path(
+    get,
+    path = "/live/current",
+    params(
+        ("lat" = Option<f64>, Query, description = "Latitude (with 'lon')"),
+        ("lon" = Option<f64>, Query, description = "Longitude (with 'lat')"),
+        ("zipcode" = Option<String>, Query, description = "Postal code (with 'country_code')"),
+        ("country_code" = Option<String>, Query, description = "ISO country code for 'zipcode'"),
+        ("city_name" = Option<String>, Query, description = "City name")
+    ),
+    responses(
+        (status = 200, description = "Current conditions", body = Conditions),
+        (status = 400, description = "Invalid or missing location", body = ErrorResponse),
+        (status = 500, description = "OPENWEATHER_API_KEY is not configured", body = ErrorResponse),
+        (status = 502, description = "Upstream request failed", body = ErrorResponse)
+    ),
+    tag = "Live"
+)]
+#[get("/live/current")]
+async fn current(cache: web::Data<LiveCache>, query: web::Query<LiveQuery>) -> impl Responder {
+    let location = match query.resolve() {
+        Ok(location) => location,
+        Err(detail) => return HttpResponse::BadRequest().json(detail),
+    };
+
+    let cache_key = location.cache_key();
+    if let Some(conditions) = cache.get_conditions(&cache_key) {
+        return HttpResponse::Ok().json(conditions);
+    }
+
+    let key = match api_key() {
+        Ok(key) => key,
+        Err(detail) => return HttpResponse::InternalServerError().json(detail),
+    };
+
+    match fetch::<owm::Current>(&cache.client, "weather", location.params(), &key).await {
+        Ok(current) => {
+            let conditions = Conditions::from(current);
+            cache.put_conditions(cache_key, conditions.clone());
+            HttpResponse::Ok().json(conditions)
+        }
+        Err(detail) => HttpResponse::BadGateway().json(detail),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/live/forecast",
+    params(
+        ("lat" = Option<f64>, Query, description = "Latitude (with 'lon')"),
+        ("lon" = Option<f64>, Query, description = "Longitude (with 'lat')"),
+        ("zipcode" = Option<String>, Query, description = "Postal code (with 'country_code')"),
+        ("country_code" = Option<String>, Query, description = "ISO country code for 'zipcode'"),
+        ("city_name" = Option<String>, Query, description = "City name")
+    ),
+    responses(
+        (status = 200, description = "Short-range forecast", body = Forecast),
+        (status = 400, description = "Invalid or missing location", body = ErrorResponse),
+        (status = 500, description = "OPENWEATHER_API_KEY is not configured", body = ErrorResponse),
+        (status = 502, description = "Upstream request failed", body = ErrorResponse)
+    ),
+    tag = "Live"
+)]
+#[get("/live/forecast")]
+async fn forecast(cache: web::Data<LiveCache>, query: web::Query<LiveQuery>) -> impl Responder {
+    let location = match query.resolve() {
+        Ok(location) => location,
+        Err(detail) => return HttpResponse::BadRequest().json(detail),
+    };
+
+    let cache_key = location.cache_key();
+    if let Some(forecast) = cache.get_forecast(&cache_key) {
+        return HttpResponse::Ok().json(forecast);
+    }
+
+    let key = match api_key() {
+        Ok(key) => key,
+        Err(detail) => return HttpResponse::InternalServerError().json(detail),
+    };
+
+    match fetch::<owm::ForecastResponse>(&cache.client, "forecast", location.params(), &key).await {
+        Ok(response) => {
+            let forecast = Forecast::from(response);
+            cache.put_forecast(cache_key, forecast.clone());
+            HttpResponse::Ok().json(forecast)
+        }
+        Err(detail) => HttpResponse::BadGateway().json(detail),
+    }
+}
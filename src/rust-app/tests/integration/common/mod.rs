@@ -0,0 +1,36 @@
+use std::net::TcpListener;
+
+/// A running instance of the real server, bound to an ephemeral port, plus an
+/// HTTP client pointed at it. Dropping the value leaves the background runtime
+/// to tear the server down with the test thread.
+pub struct TestApp {
+    pub address: String,
+    pub client: awc::Client,
+}
+
+impl TestApp {
+    /// Build an absolute URL onto the running server for the given path.
+    pub fn url(&self, path: &str) -> String {
+        format!("{}{}", self.address, path)
+    }
+}
+
+/// Boot the real `HttpServer` on a port the OS picks for us, then spawn it on
+/// the current Actix runtime so the returned [`TestApp`] can reach it over a
+/// socket. Binding the listener here lets us read the port back before the
+/// server starts accepting connections.
+pub async fn spawn_app() -> TestApp {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("failed to bind ephemeral port");
+    let port = listener
+        .local_addr()
+        .expect("failed to read bound address")
+        .port();
+
+    let server = rust_app::run_listener(listener).expect("failed to start server");
+    actix_web::rt::spawn(server);
+
+    TestApp {
+        address: format!("http://127.0.0.1:{}", port),
+        client: awc::Client::default(),
+    }
+}
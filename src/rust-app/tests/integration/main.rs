@@ -0,0 +1,83 @@
+//! Blackbox integration tests that drive the real server over a socket,
+//! complementing the in-process `test::init_service` unit tests in the crate.
+
+use actix_web::http::StatusCode;
+use actix_web::http::header::LOCATION;
+
+mod common;
+
+use common::spawn_app;
+
+#[actix_web::test]
+async fn root_redirects_to_docs() {
+    let app = spawn_app().await;
+
+    let resp = app
+        .client
+        .get(app.url("/"))
+        .send()
+        .await
+        .expect("request to / failed");
+
+    assert_eq!(resp.status(), StatusCode::MOVED_PERMANENTLY);
+    let location = resp
+        .headers()
+        .get(LOCATION)
+        .expect("missing LOCATION header");
+    assert_eq!(location, "/docs/");
+}
+
+#[actix_web::test]
+async fn docs_redirects_to_trailing_slash() {
+    let app = spawn_app().await;
+
+    let resp = app
+        .client
+        .get(app.url("/docs"))
+        .send()
+        .await
+        .expect("request to /docs failed");
+
+    assert_eq!(resp.status(), StatusCode::MOVED_PERMANENTLY);
+    let location = resp
+        .headers()
+        .get(LOCATION)
+        .expect("missing LOCATION header");
+    assert_eq!(location, "/docs/");
+}
+
+#[actix_web::test]
+async fn openapi_json_is_served() {
+    let app = spawn_app().await;
+
+    let mut resp = app
+        .client
+        .get(app.url("/api-doc/openapi.json"))
+        .send()
+        .await
+        .expect("request to openapi.json failed");
+
+    assert_eq!(resp.status(), StatusCode::OK);
+    let doc: serde_json::Value = resp.json().await.expect("openapi response not json");
+    assert!(doc.get("paths").is_some(), "openapi doc missing paths");
+    assert!(
+        doc["paths"].get("/countries").is_some(),
+        "openapi doc missing /countries path"
+    );
+}
+
+#[actix_web::test]
+async fn unknown_country_returns_error_body() {
+    let app = spawn_app().await;
+
+    let mut resp = app
+        .client
+        .get(app.url("/countries/Nowhere"))
+        .send()
+        .await
+        .expect("request to /countries/Nowhere failed");
+
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    let body: serde_json::Value = resp.json().await.expect("error response not json");
+    assert_eq!(body["detail"], "Country 'Nowhere' not found");
+}